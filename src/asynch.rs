@@ -0,0 +1,97 @@
+use crate::read_fsm::{ReadStateMachine, ReadStatus, UNBOUNDED_SEARCH_RETRIES};
+use crate::{
+    create_command, Error, OutputFrame, Response, ACTIVE_MODE_RESPONSE, OUTPUT_FRAME_SIZE,
+    PASSIVE_MODE_RESPONSE, RESPONSE_FRAME_SIZE, SLEEP_RESPONSE,
+};
+use embedded_io_async::{Read, Write};
+
+/// Async counterpart to [`crate::Pms7003Sensor`], built on `embedded-io-async`
+/// instead of the blocking, `nb`-based `embedded-hal` serial traits.
+///
+/// Reads and writes `.await` on the underlying transport rather than
+/// spinning, so the sensor can share an executor with other async tasks
+/// instead of busy-looping a core.
+pub struct AsyncPms7003Sensor<Serial>
+where
+    Serial: Read + Write,
+{
+    serial: Serial,
+}
+
+impl<Serial> AsyncPms7003Sensor<Serial>
+where
+    Serial: Read + Write,
+{
+    /// Creates a new sensor instance
+    /// * `serial` - object implementing `embedded-io-async` read/write traits
+    pub fn new(serial: Serial) -> Self {
+        Self { serial }
+    }
+
+    async fn read_from_device<T: AsMut<[u8]>>(&mut self, mut buffer: T) -> Result<T, Error> {
+        // `retry()` fires on every non-matching byte while hunting for the
+        // magic numbers, not only on a blocking error, so a small count here
+        // would fail on the first byte of leading noise instead of awaiting
+        // past it. Progress is still bounded - by the bytes actually read.
+        let mut read = ReadStateMachine::new(buffer.as_mut(), UNBOUNDED_SEARCH_RETRIES);
+        let mut byte = [0u8; 1];
+        loop {
+            self.serial
+                .read_exact(&mut byte)
+                .await
+                .map_err(|_| Error::ReadFailed)?;
+
+            match read.update::<core::convert::Infallible>(Ok(byte[0])) {
+                ReadStatus::Failed => return Err(Error::ReadFailed),
+                ReadStatus::Finished => return Ok(buffer),
+                ReadStatus::InProgress => {}
+            }
+        }
+    }
+
+    /// Reads sensor status. Awaits until a status is available.
+    pub async fn read(&mut self) -> Result<OutputFrame, Error> {
+        OutputFrame::from_buffer(&self.read_from_device([0_u8; OUTPUT_FRAME_SIZE]).await?)
+    }
+
+    pub async fn sleep(&mut self) -> Result<(), Error> {
+        self.send_cmd(&create_command(0xe4, 0)).await?;
+        self.receive_response(SLEEP_RESPONSE).await
+    }
+
+    pub async fn wake(&mut self) -> Result<(), Error> {
+        self.send_cmd(&create_command(0xe4, 1)).await
+    }
+
+    /// Passive mode - sensor reports air quality on request
+    pub async fn passive(&mut self) -> Result<(), Error> {
+        self.send_cmd(&create_command(0xe1, 0)).await?;
+        self.receive_response(PASSIVE_MODE_RESPONSE).await
+    }
+
+    /// Active mode - sensor reports air quality continuously
+    pub async fn active(&mut self) -> Result<(), Error> {
+        self.send_cmd(&create_command(0xe1, 1)).await?;
+        self.receive_response(ACTIVE_MODE_RESPONSE).await
+    }
+
+    /// Requests status in passive mode
+    pub async fn request(&mut self) -> Result<(), Error> {
+        self.send_cmd(&create_command(0xe2, 0)).await
+    }
+
+    async fn send_cmd(&mut self, cmd: &[u8]) -> Result<(), Error> {
+        self.serial
+            .write_all(cmd)
+            .await
+            .map_err(|_| Error::SendFailed)
+    }
+
+    async fn receive_response(&mut self, expected_response: Response) -> Result<(), Error> {
+        if self.read_from_device([0u8; RESPONSE_FRAME_SIZE]).await? != expected_response {
+            Err(Error::IncorrectResponse)
+        } else {
+            Ok(())
+        }
+    }
+}
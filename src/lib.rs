@@ -1,11 +1,18 @@
 #![no_std]
 
+use embedded_hal::blocking::delay::DelayUs;
 use embedded_hal::serial::{Read, Write};
 use nb::block;
 use scroll::{Pread, Pwrite, BE};
 
 mod read_fsm;
 
+#[cfg(feature = "async")]
+mod asynch;
+
+#[cfg(feature = "async")]
+pub use asynch::AsyncPms7003Sensor;
+
 const CMD_FRAME_SIZE: usize = 7;
 const OUTPUT_FRAME_SIZE: usize = 32;
 const RESPONSE_FRAME_SIZE: usize = 8;
@@ -19,6 +26,7 @@ const PASSIVE_MODE_RESPONSE: Response = [MN1, MN1, 0x00, 0x04, 0xE1, 0x00, 0x01,
 const ACTIVE_MODE_RESPONSE: Response = [MN1, MN2, 0x00, 0x04, 0xE1, 0x01, 0x01, 0x75];
 const SLEEP_RESPONSE: Response = [MN1, MN2, 0x00, 0x04, 0xE4, 0x00, 0x01, 0x77];
 
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Debug)]
 pub enum Error {
     SendFailed,
@@ -26,17 +34,110 @@ pub enum Error {
     ChecksumError,
     IncorrectResponse,
     NoResponse,
+    Timeout,
+}
+
+/// Default number of read retries used when neither [`Pms7003Sensor::with_retries`]
+/// nor [`Pms7003Sensor::with_timeout`] has been called.
+const DEFAULT_RETRIES: usize = 100;
+
+/// Placeholder `Delay` used until [`Pms7003Sensor::with_timeout`] supplies a
+/// real one. It is never actually invoked: a plain [`ReadDeadline::Retries`]
+/// deadline doesn't touch the delay at all.
+pub struct NoDelay;
+
+impl DelayUs<u32> for NoDelay {
+    fn delay_us(&mut self, _us: u32) {}
+}
+
+/// How long [`Pms7003Sensor::read_from_device`] should keep waiting for a
+/// frame before giving up.
+enum ReadDeadline<Delay> {
+    /// Give up with [`Error::ReadFailed`] after a fixed number of reads that
+    /// made the `ReadStateMachine` retry, regardless of how long that actually
+    /// took - the original, CPU-speed-dependent behaviour.
+    Retries(usize),
+    /// Give up with [`Error::Timeout`] once `timeout_us` microseconds of
+    /// wall-clock time, measured via `delay`, have elapsed since the start of
+    /// the current read.
+    Timeout {
+        delay: Delay,
+        /// The budget configured via [`Pms7003Sensor::with_timeout`], reapplied
+        /// to `remaining_us` at the start of every [`Pms7003Sensor::read_from_device`]
+        /// call - the deadline is a per-read window, not a lifetime-cumulative one.
+        timeout_us: u32,
+        remaining_us: u32,
+    },
+}
+
+impl<Delay> ReadDeadline<Delay>
+where
+    Delay: DelayUs<u32>,
+{
+    const TICK_US: u32 = 1000;
+
+    fn retries(&self) -> usize {
+        match self {
+            ReadDeadline::Retries(retries) => *retries,
+            ReadDeadline::Timeout { .. } => usize::MAX,
+        }
+    }
+
+    /// Resets the wall-clock budget back to `timeout_us`, so each call to
+    /// `read_from_device` gets its own fresh window instead of draining a
+    /// budget shared across the sensor's whole lifetime. A `Retries`
+    /// deadline has no state to reset.
+    fn reset(&mut self) {
+        if let ReadDeadline::Timeout {
+            timeout_us,
+            remaining_us,
+            ..
+        } = self
+        {
+            *remaining_us = *timeout_us;
+        }
+    }
+
+    /// Called once per loop iteration of `read_from_device` that made the
+    /// `ReadStateMachine` retry (idle waiting, a read error, or a non-matching
+    /// byte while hunting for `MN1`; not a matched byte, and not a byte that
+    /// only breaks an `MN1`/`MN2` pair) - see the `retries_remaining` check
+    /// at the call site. Advances
+    /// the deadline and returns `Error::Timeout` once it has passed. A
+    /// `Retries` deadline always returns `Ok`, since its countdown already
+    /// lives inside the `ReadStateMachine` itself.
+    fn tick(&mut self) -> Result<(), Error> {
+        match self {
+            ReadDeadline::Retries(_) => Ok(()),
+            ReadDeadline::Timeout {
+                delay,
+                remaining_us,
+                ..
+            } => {
+                if *remaining_us == 0 {
+                    return Err(Error::Timeout);
+                }
+                let step = (*remaining_us).min(Self::TICK_US);
+                delay.delay_us(step);
+                *remaining_us -= step;
+                Ok(())
+            }
+        }
+    }
 }
 
 /// Sensor interface
-pub struct Pms7003Sensor<Serial>
+pub struct Pms7003Sensor<Serial, Delay = NoDelay>
 where
     Serial: Read<u8> + Write<u8>,
 {
     serial: Serial,
+    deadline: ReadDeadline<Delay>,
+    poll_buffer: [u8; OUTPUT_FRAME_SIZE],
+    poll_progress: Option<read_fsm::Progress>,
 }
 
-impl<Serial> Pms7003Sensor<Serial>
+impl<Serial> Pms7003Sensor<Serial, NoDelay>
 where
     Serial: Read<u8> + Write<u8>,
 {
@@ -49,15 +150,84 @@ where
             }
         }
 
-        Self { serial }
+        Self {
+            serial,
+            deadline: ReadDeadline::Retries(DEFAULT_RETRIES),
+            poll_buffer: [0_u8; OUTPUT_FRAME_SIZE],
+            poll_progress: None,
+        }
     }
+}
 
+impl<Serial, Delay> Pms7003Sensor<Serial, Delay>
+where
+    Serial: Read<u8> + Write<u8>,
+{
+    /// Fails a read with [`Error::ReadFailed`] once `retries` reads have made
+    /// the read state machine retry (a `WouldBlock`, a read error, or a
+    /// non-matching byte while hunting for `MN1`) and one more such read
+    /// follows, instead of the wall-clock deadline configured by
+    /// [`Pms7003Sensor::with_timeout`]. The count covers the whole read and is
+    /// not reset by bytes that make progress.
+    pub fn with_retries(self, retries: usize) -> Pms7003Sensor<Serial, NoDelay> {
+        Pms7003Sensor {
+            serial: self.serial,
+            deadline: ReadDeadline::Retries(retries),
+            poll_buffer: self.poll_buffer,
+            poll_progress: self.poll_progress,
+        }
+    }
+
+    /// Fails a read with [`Error::Timeout`] once `timeout_us` microseconds
+    /// have elapsed without a complete frame, measured via `delay` rather
+    /// than a CPU-speed-dependent retry count.
+    pub fn with_timeout<NewDelay>(
+        self,
+        delay: NewDelay,
+        timeout_us: u32,
+    ) -> Pms7003Sensor<Serial, NewDelay>
+    where
+        NewDelay: DelayUs<u32>,
+    {
+        Pms7003Sensor {
+            serial: self.serial,
+            deadline: ReadDeadline::Timeout {
+                delay,
+                timeout_us,
+                remaining_us: timeout_us,
+            },
+            poll_buffer: self.poll_buffer,
+            poll_progress: self.poll_progress,
+        }
+    }
+}
+
+impl<Serial, Delay> Pms7003Sensor<Serial, Delay>
+where
+    Serial: Read<u8> + Write<u8>,
+    Delay: DelayUs<u32>,
+{
     fn read_from_device<T: AsMut<[u8]>>(&mut self, mut buffer: T) -> Result<T, Error> {
         use read_fsm::*;
 
-        let mut read = ReadStateMachine::new(buffer.as_mut());
+        self.deadline.reset();
+
+        let mut read = ReadStateMachine::new(buffer.as_mut(), self.deadline.retries());
         loop {
-            match read.update(self.serial.read()) {
+            let result = self.serial.read();
+            let retries_before = read.retries_remaining();
+            let status = read.update(result);
+
+            // Only charge the deadline for iterations that didn't make
+            // genuine progress on the frame (idle waiting, or noise that
+            // made the FSM retry) - `retries_remaining` is unchanged by a
+            // matched byte, so ticking on every iteration regardless would
+            // burn the whole budget on a normal, successful read.
+            if read.retries_remaining() != retries_before {
+                self.deadline.tick()?;
+            }
+
+            match status {
                 ReadStatus::Failed => return Err(Error::ReadFailed),
                 ReadStatus::Finished => return Ok(buffer),
                 ReadStatus::InProgress => {}
@@ -70,6 +240,53 @@ where
         OutputFrame::from_buffer(&self.read_from_device([0_u8; OUTPUT_FRAME_SIZE])?)
     }
 
+    /// Non-blocking read driven by whatever the serial peripheral currently
+    /// has available. Unlike [`Pms7003Sensor::read`], this never spins
+    /// waiting for the rest of a frame: it feeds what it gets into the read
+    /// state machine and returns `WouldBlock` as soon as the peripheral does,
+    /// remembering how far it got so the next call picks up where this one
+    /// left off. This lets a caller drive reads from a poll loop (e.g. an
+    /// interrupt-fed ring buffer) without ever blocking the core.
+    pub fn poll_read(&mut self) -> nb::Result<OutputFrame, Error> {
+        use read_fsm::*;
+
+        let Self {
+            serial,
+            poll_buffer,
+            poll_progress,
+            ..
+        } = self;
+
+        // `retry()` fires on every non-matching byte while hunting for the
+        // magic numbers, not only on `WouldBlock` - a small budget here would
+        // make this fail on the very first byte of leading noise instead of
+        // searching past it, so give it an effectively unbounded one.
+        let mut read = match poll_progress.take() {
+            Some(progress) => ReadStateMachine::resume(poll_buffer, progress),
+            None => ReadStateMachine::new(poll_buffer, UNBOUNDED_SEARCH_RETRIES),
+        };
+
+        loop {
+            let byte = match serial.read() {
+                Ok(byte) => byte,
+                Err(nb::Error::WouldBlock) => {
+                    *poll_progress = Some(read.progress());
+                    return Err(nb::Error::WouldBlock);
+                }
+                Err(nb::Error::Other(_)) => return Err(nb::Error::Other(Error::ReadFailed)),
+            };
+
+            let (_, status) = read.feed(&[byte]);
+            match status {
+                ReadStatus::Failed => return Err(nb::Error::Other(Error::ReadFailed)),
+                ReadStatus::Finished => {
+                    return OutputFrame::from_buffer(&*poll_buffer).map_err(nb::Error::Other)
+                }
+                ReadStatus::InProgress => {}
+            }
+        }
+    }
+
     pub fn sleep(&mut self) -> Result<(), Error> {
         self.send_cmd(&create_command(0xe4, 0))?;
         self.receive_response(SLEEP_RESPONSE)
@@ -134,6 +351,7 @@ fn create_command(cmd: u8, data: u16) -> [u8; CMD_FRAME_SIZE] {
 }
 
 /// Contains data reported by the sensor
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Default, Debug)]
 pub struct OutputFrame {
     pub start1: u8,
@@ -192,6 +410,36 @@ impl OutputFrame {
     }
 }
 
+/// Scans `bytes` for a complete frame and decodes it, independent of any
+/// `embedded-hal` transport. Tolerates leading garbage before the `MN1`/`MN2`
+/// magic numbers, so it can be pointed directly at a captured log, an MQTT
+/// payload or a DMA ring buffer snapshot.
+///
+/// Only the first frame found is decoded: if its checksum is wrong this
+/// returns [`Error::ChecksumError`] and the rest of `bytes` is not searched.
+/// If no complete frame is found it returns [`Error::NoResponse`].
+///
+/// On success, returns the decoded frame together with the number of bytes
+/// consumed from the start of `bytes`, so the caller can advance its own
+/// cursor past it.
+pub fn parse_frame(bytes: &[u8]) -> Result<(OutputFrame, usize), Error> {
+    let mut buffer = [0u8; OUTPUT_FRAME_SIZE];
+
+    // `retry()` fires on every non-matching byte while hunting for the magic
+    // numbers, not only on a blocking error - a small budget here would
+    // contradict the "tolerates leading garbage" promise above by failing on
+    // the first noise byte. Progress is still bounded, by `bytes.len()`.
+    let mut read =
+        read_fsm::ReadStateMachine::new(&mut buffer, read_fsm::UNBOUNDED_SEARCH_RETRIES);
+
+    match read.feed(bytes) {
+        (consumed, read_fsm::ReadStatus::Finished) => {
+            OutputFrame::from_buffer(&buffer).map(|frame| (frame, consumed))
+        }
+        _ => Err(Error::NoResponse),
+    }
+}
+
 impl<TX, RX> Pms7003Sensor<Wrapper<TX, RX>>
 where
     TX: Write<u8>,
@@ -1,5 +1,6 @@
 use crate::{MN1, MN2};
 
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(PartialEq, Debug)]
 pub enum ReadStatus {
     InProgress,
@@ -7,6 +8,7 @@ pub enum ReadStatus {
     Failed,
 }
 
+#[derive(Clone, Copy)]
 enum State {
     WaitingForFirstMagicNumber,
     WaitingForSecondMagicNumber,
@@ -15,6 +17,16 @@ enum State {
     Failed,
 }
 
+/// Retry budget for callers that drive [`ReadStateMachine`] a byte (or chunk)
+/// at a time via [`ReadStateMachine::feed`] or [`ReadStateMachine::update`] -
+/// e.g. [`crate::Pms7003Sensor::poll_read`], the async driver, and [`crate::parse_frame`]. `retry()` is consulted for
+/// *every* byte that doesn't match while hunting for `MN1`/`MN2`, not only on
+/// a blocking error, so a small budget would make these callers fail on the
+/// first byte of leading garbage instead of searching past it. There's no
+/// matching risk of spinning forever: progress is bounded by the finite input
+/// already being fed (a byte slice, or one real peripheral read per call).
+pub(crate) const UNBOUNDED_SEARCH_RETRIES: usize = usize::MAX;
+
 /// State machine representing ongoing read from device
 /// * Waits for magic numbers
 /// * Allows for breaks in transmission
@@ -27,12 +39,12 @@ pub struct ReadStateMachine<'a> {
 }
 
 impl<'a> ReadStateMachine<'a> {
-    pub fn new(buffer: &'a mut [u8]) -> Self {
+    pub fn new(buffer: &'a mut [u8], retries: usize) -> Self {
         Self {
             buffer,
             index: 0,
             state: State::WaitingForFirstMagicNumber,
-            retries: 100,
+            retries,
         }
     }
 
@@ -44,6 +56,17 @@ impl<'a> ReadStateMachine<'a> {
         }
     }
 
+    /// Retry budget remaining. Unchanged by an `update` call that matched a
+    /// byte and made genuine progress; decremented by one that retried (idle
+    /// waiting, a read error, or a non-matching byte while waiting for `MN1`).
+    /// A byte that only breaks an `MN1`/`MN2` pair restarts the search without
+    /// changing it. Callers can compare this before and after an `update` to
+    /// tell retries apart, e.g. to decide whether a wall-clock deadline should
+    /// be charged for that iteration.
+    pub(crate) fn retries_remaining(&self) -> usize {
+        self.retries
+    }
+
     fn magic_number_read(&mut self) {
         self.index = 2;
         self.buffer[0] = MN1;
@@ -85,13 +108,58 @@ impl<'a> ReadStateMachine<'a> {
             State::Failed => ReadStatus::Failed,
         }
     }
+
+    /// Feeds as many `bytes` as are needed to make progress, stopping as soon
+    /// as the frame completes (or fails) rather than discarding the rest of
+    /// the slice. Returns the number of bytes actually consumed together with
+    /// the resulting status, so a caller reading from a ring buffer can leave
+    /// the unconsumed tail in place for the next frame.
+    pub fn feed(&mut self, bytes: &[u8]) -> (usize, ReadStatus) {
+        for (consumed, &byte) in bytes.iter().enumerate() {
+            match self.update::<core::convert::Infallible>(Ok(byte)) {
+                ReadStatus::InProgress => continue,
+                status => return (consumed + 1, status),
+            }
+        }
+
+        (bytes.len(), ReadStatus::InProgress)
+    }
+
+    /// Snapshot of the progress made so far, to be resumed later via
+    /// [`ReadStateMachine::resume`] once more bytes are available.
+    pub(crate) fn progress(&self) -> Progress {
+        Progress {
+            index: self.index,
+            state: self.state,
+            retries: self.retries,
+        }
+    }
+
+    /// Resumes a state machine that was previously interrupted mid-frame,
+    /// e.g. because a non-blocking read only had part of a frame available.
+    pub(crate) fn resume(buffer: &'a mut [u8], progress: Progress) -> Self {
+        Self {
+            buffer,
+            index: progress.index,
+            state: progress.state,
+            retries: progress.retries,
+        }
+    }
+}
+
+/// Opaque, buffer-independent snapshot of a [`ReadStateMachine`]'s progress.
+#[derive(Clone, Copy)]
+pub(crate) struct Progress {
+    index: usize,
+    state: State,
+    retries: usize,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn create_test_fsm(buffer: &mut [u8], retries: usize) -> ReadStateMachine {
+    fn create_test_fsm(buffer: &mut [u8], retries: usize) -> ReadStateMachine<'_> {
         ReadStateMachine {
             buffer,
             index: 0,
@@ -231,4 +299,40 @@ mod tests {
             fsm.update::<()>(Err(nb::Error::WouldBlock))
         );
     }
+
+    #[test]
+    fn feed_skips_leading_garbage_and_reports_bytes_consumed() {
+        let mut buffer = [0u8; 4];
+        let mut fsm = create_test_fsm(&mut buffer, UNBOUNDED_SEARCH_RETRIES);
+
+        let (consumed, status) =
+            fsm.feed(&[0x00, 0x00, 0x00, MN1, MN2, 0x11, 0x33, 0xFF, 0xFF]);
+
+        assert_eq!(ReadStatus::Finished, status);
+        assert_eq!(7, consumed);
+        assert_eq!([MN1, MN2, 0x11, 0x33], buffer);
+    }
+
+    #[test]
+    fn feed_stops_consuming_once_the_frame_is_finished() {
+        let mut buffer = [0u8; 4];
+        let mut fsm = create_test_fsm(&mut buffer, UNBOUNDED_SEARCH_RETRIES);
+
+        let (consumed, status) = fsm.feed(&[MN1, MN2, 0x11, 0x33, 0xAA, 0xBB]);
+
+        assert_eq!(ReadStatus::Finished, status);
+        assert_eq!(4, consumed);
+        assert_eq!([MN1, MN2, 0x11, 0x33], buffer);
+    }
+
+    #[test]
+    fn feed_reports_in_progress_when_bytes_run_out_first() {
+        let mut buffer = [0u8; 4];
+        let mut fsm = create_test_fsm(&mut buffer, UNBOUNDED_SEARCH_RETRIES);
+
+        let (consumed, status) = fsm.feed(&[MN1, MN2, 0x11]);
+
+        assert_eq!(ReadStatus::InProgress, status);
+        assert_eq!(3, consumed);
+    }
 }
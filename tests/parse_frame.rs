@@ -0,0 +1,26 @@
+use pms_7003::parse_frame;
+
+// Note: this crate's `MN1`/`MN2` are both `0x42`, so unlike a real PMS7003
+// "BM" preamble the second byte here must also be `0x42` for the frame to
+// actually decode.
+const FRAME: [u8; 32] = [
+    0x42, 0x42, 0x0, 0x1c, 0x0, 0x5, 0x0, 0x7, 0x0, 0x7, 0x0, 0x5, 0x0, 0x7, 0x0, 0x7, 0x0, 0x0,
+    0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0xc6,
+];
+
+#[test]
+fn parses_a_frame_at_the_start_of_the_buffer() {
+    let (frame, consumed) = parse_frame(&FRAME).expect("frame should decode");
+    assert_eq!(frame.pm2_5, 7);
+    assert_eq!(consumed, FRAME.len());
+}
+
+#[test]
+fn tolerates_leading_garbage_before_the_frame() {
+    let mut bytes = vec![0xAA, 0x00, 0xFF];
+    bytes.extend(FRAME);
+
+    let (frame, consumed) = parse_frame(&bytes).expect("frame should decode despite noise");
+    assert_eq!(frame.pm2_5, 7);
+    assert_eq!(consumed, bytes.len());
+}
@@ -0,0 +1,72 @@
+use pms_7003::Pms7003Sensor;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+#[derive(Clone)]
+struct QueueRx(Rc<RefCell<VecDeque<u8>>>);
+
+impl embedded_hal::serial::Read<u8> for QueueRx {
+    type Error = ();
+
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        self.0.borrow_mut().pop_front().ok_or(nb::Error::WouldBlock)
+    }
+}
+
+struct NoopTx;
+
+impl embedded_hal::serial::Write<u8> for NoopTx {
+    type Error = ();
+
+    fn write(&mut self, _: u8) -> nb::Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+// Note: this crate's `MN1`/`MN2` are both `0x42`, so unlike a real PMS7003
+// "BM" preamble the second byte here must also be `0x42` for the frame to
+// actually decode.
+const FRAME: [u8; 32] = [
+    0x42, 0x42, 0x0, 0x1c, 0x0, 0x5, 0x0, 0x7, 0x0, 0x7, 0x0, 0x5, 0x0, 0x7, 0x0, 0x7, 0x0, 0x0,
+    0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0xc6,
+];
+
+#[test]
+fn poll_read_would_block_until_bytes_arrive() {
+    let queue = Rc::new(RefCell::new(VecDeque::new()));
+    let mut pms = Pms7003Sensor::new_tx_rx(NoopTx, QueueRx(queue.clone()));
+
+    assert!(matches!(pms.poll_read(), Err(nb::Error::WouldBlock)));
+}
+
+#[test]
+fn poll_read_skips_leading_noise_and_decodes_a_frame() {
+    let queue = Rc::new(RefCell::new(VecDeque::new()));
+    let mut pms = Pms7003Sensor::new_tx_rx(NoopTx, QueueRx(queue.clone()));
+
+    queue.borrow_mut().extend([0xAA, 0x00, 0xFF]);
+    queue.borrow_mut().extend(FRAME);
+
+    let output = pms
+        .poll_read()
+        .expect("frame should decode despite leading noise");
+    assert_eq!(output.pm2_5, 7);
+}
+
+#[test]
+fn poll_read_resumes_progress_across_would_block() {
+    let queue = Rc::new(RefCell::new(VecDeque::new()));
+    let mut pms = Pms7003Sensor::new_tx_rx(NoopTx, QueueRx(queue.clone()));
+
+    queue.borrow_mut().extend(FRAME[..10].iter().copied());
+    assert!(matches!(pms.poll_read(), Err(nb::Error::WouldBlock)));
+
+    queue.borrow_mut().extend(FRAME[10..].iter().copied());
+    let output = pms.poll_read().expect("remaining bytes complete the frame");
+    assert_eq!(output.pm2_5, 7);
+}
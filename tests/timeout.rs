@@ -0,0 +1,120 @@
+use embedded_hal::blocking::delay::DelayUs;
+use pms_7003::{Error, Pms7003Sensor};
+use std::cell::Cell;
+use std::rc::Rc;
+
+struct NoisyRx {
+    // `Pms7003Sensor::new` drains the serial until a read errors, so the very
+    // first call has to `WouldBlock` once; every call after that is the
+    // "noisy" behaviour under test.
+    drained: Cell<bool>,
+}
+
+impl embedded_hal::serial::Read<u8> for NoisyRx {
+    type Error = ();
+
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        if !self.drained.replace(true) {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        // Never `WouldBlock`s and never matches a magic number - the
+        // peripheral misbehaving in a way that never gives the old
+        // WouldBlock-gated deadline a chance to fire.
+        Ok(0x00)
+    }
+}
+
+struct NoopTx;
+
+impl embedded_hal::serial::Write<u8> for NoopTx {
+    type Error = ();
+
+    fn write(&mut self, _: u8) -> nb::Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+struct NoopDelay;
+
+impl DelayUs<u32> for NoopDelay {
+    fn delay_us(&mut self, _us: u32) {}
+}
+
+#[test]
+fn timeout_fires_even_when_the_peripheral_never_blocks() {
+    let mut pms = Pms7003Sensor::new_tx_rx(
+        NoopTx,
+        NoisyRx {
+            drained: Cell::new(false),
+        },
+    )
+    .with_timeout(NoopDelay, 10_000);
+
+    assert!(matches!(pms.read(), Err(Error::Timeout)));
+}
+
+// Note: this crate's `MN1`/`MN2` are both `0x42`, so unlike a real PMS7003
+// "BM" preamble the second byte here must also be `0x42` for the frame to
+// actually decode.
+const FRAME: [u8; 32] = [
+    0x42, 0x42, 0x0, 0x1c, 0x0, 0x5, 0x0, 0x7, 0x0, 0x7, 0x0, 0x5, 0x0, 0x7, 0x0, 0x7, 0x0, 0x0,
+    0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0xc6,
+];
+
+/// Replays a fixed, scripted sequence of reads - `None` standing in for a
+/// `WouldBlock` - so a test can control exactly which loop iterations of
+/// `read_from_device` count as "idle" (and so get charged against a timeout
+/// budget) versus genuine byte-matching progress (which doesn't).
+struct ScriptedRx {
+    steps: Rc<Vec<Option<u8>>>,
+    next: Cell<usize>,
+}
+
+impl embedded_hal::serial::Read<u8> for ScriptedRx {
+    type Error = ();
+
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        let index = self.next.replace(self.next.get() + 1);
+        match self.steps[index] {
+            Some(byte) => Ok(byte),
+            None => Err(nb::Error::WouldBlock),
+        }
+    }
+}
+
+#[test]
+fn timeout_budget_is_not_carried_over_from_the_previous_read() {
+    // Budget for exactly two `tick()`s. The first read below spends both of
+    // them on idle `WouldBlock`s before a clean frame arrives - leaving
+    // `remaining_us` at 0 once it finishes. The second read only needs one
+    // idle `WouldBlock`, which is well within a *fresh* budget, but would
+    // fail instantly against the stale, already-exhausted one if
+    // `read_from_device` didn't reset it first.
+    let mut steps = vec![None, None, None];
+    steps.extend(FRAME.iter().copied().map(Some));
+    steps.push(None);
+    steps.extend(FRAME.iter().copied().map(Some));
+    let steps = Rc::new(steps);
+
+    let mut pms = Pms7003Sensor::new_tx_rx(
+        NoopTx,
+        ScriptedRx {
+            steps,
+            next: Cell::new(0),
+        },
+    )
+    .with_timeout(NoopDelay, 2_000);
+
+    let first = pms.read().expect("first read should decode a clean frame");
+    assert_eq!(first.pm2_5, 7);
+
+    let second = pms
+        .read()
+        .expect("second read should get a fresh timeout budget, not the first read's leftovers");
+    assert_eq!(second.pm2_5, 7);
+}